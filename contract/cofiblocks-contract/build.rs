@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+
+use cainome::rs::Abigen;
+
+/// Regenerates the typed ERC-1155 bindings from the contract's Sierra ABI.
+///
+/// This runs `cainome`'s [`Abigen`] builder against `abi/erc1155.abi.json` and writes the
+/// resulting module to `src/erc1155.generated.rs`, which `src/erc1155.rs` then `include!`s. The
+/// generated file is git-ignored; re-run on every build so it stays in sync whenever the ABI
+/// changes. (`abigen!` is a proc macro meant to be invoked from crate code, not from a build
+/// script as a file generator — it has no file-emitting form, hence the builder here instead.)
+fn main() {
+    let abi_path = PathBuf::from("abi/erc1155.abi.json");
+    println!("cargo:rerun-if-changed={}", abi_path.display());
+
+    let output_path = PathBuf::from("src/erc1155.generated.rs");
+    Abigen::new("Erc1155", abi_path.to_str().unwrap())
+        .generate()
+        .expect("failed to generate erc1155 bindings")
+        .write_to_file(output_path.to_str().unwrap())
+        .expect("failed to write erc1155 bindings");
+}
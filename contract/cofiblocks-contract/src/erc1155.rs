@@ -0,0 +1,5 @@
+//! Typed bindings for the ERC-1155 contract, generated at build time by `build.rs` from
+//! `abi/erc1155.abi.json` via `cainome`'s `Abigen` builder. The generated file is git-ignored;
+//! regenerate it by building the crate after touching the ABI.
+
+include!("erc1155.generated.rs");
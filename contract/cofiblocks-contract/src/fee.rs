@@ -0,0 +1,57 @@
+//! Fee-token selection and balance checks for deployment transactions.
+
+use std::sync::Arc;
+
+use starknet::{
+    core::types::{BlockId, BlockTag, FieldElement, FunctionCall},
+    core::utils::get_selector_from_name,
+    macros::felt,
+    providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider},
+};
+
+/// Which token a deployment transaction pays its fee in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum FeeToken {
+    /// Legacy v1 transactions, fee paid in ETH.
+    #[default]
+    Eth,
+    /// v3 transactions, fee paid in STRK via the resource-bounds model.
+    Strk,
+}
+
+impl FeeToken {
+    /// The token's well-known ERC-20 contract address, the same on every Starknet network.
+    fn contract_address(&self) -> FieldElement {
+        match self {
+            FeeToken::Eth => {
+                felt!("0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc5")
+            }
+            FeeToken::Strk => {
+                felt!("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d")
+            }
+        }
+    }
+}
+
+/// Reads `account`'s balance of `token` via the standard ERC-20 `balanceOf` view call, returning
+/// it as a single felt (balances fit in ETH/STRK's low 128 bits for any realistic deployment
+/// fee, so the `u256`'s high limb is ignored).
+pub async fn fee_token_balance(
+    client: &Arc<JsonRpcClient<HttpTransport>>,
+    token: FeeToken,
+    account: FieldElement,
+) -> FieldElement {
+    let result = client
+        .call(
+            FunctionCall {
+                contract_address: token.contract_address(),
+                entry_point_selector: get_selector_from_name("balanceOf").unwrap(),
+                calldata: vec![account],
+            },
+            BlockId::Tag(BlockTag::Pending),
+        )
+        .await
+        .unwrap();
+
+    result[0]
+}
@@ -1,24 +1,35 @@
+mod erc1155;
+pub mod events;
+mod fee;
+mod network;
+pub mod signer;
 mod u256;
 
+pub use events::{index_transfers, print_transfers_report};
+pub use fee::FeeToken;
+pub use network::{resolve_network_config, Network, NetworkConfig, NetworkOverrides};
+pub use signer::{resolve_signer, AccountType, Signer, SignerBackend};
+
 use std::{path::PathBuf, str::FromStr, sync::Arc};
 
 use cainome_cairo_serde::{ByteArray, CairoSerde};
 use serde::Deserialize;
 use starknet::{
-    accounts::{ExecutionEncoding, SingleOwnerAccount},
+    accounts::SingleOwnerAccount,
     contract::ContractFactory,
     core::{
-        chain_id,
-        types::{BlockId, BlockTag, ExecutionResult, FieldElement, FunctionCall, StarknetError},
-        utils::get_selector_from_name,
+        crypto::compute_hash_on_elements,
+        types::{BlockId, BlockTag, ExecutionResult, FieldElement, StarknetError},
+        utils::{get_udc_deployed_address, UdcUniqueness},
     },
-    macros::{felt, short_string},
     providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider, ProviderError, Url},
-    signers::{LocalWallet, SigningKey},
 };
 use thiserror::Error;
 use tokio::time::Duration;
 
+use crate::erc1155::Erc1155Reader;
+use crate::fee::{fee_token_balance, FeeToken};
+use crate::signer::{AccountType, Signer};
 use crate::u256::U256;
 
 /// Contract parameters that needs to be passed for contract creation
@@ -39,16 +50,6 @@ pub struct ContractTokensInfo {
     pub value: u64,
 }
 
-/// Supported Starknet networks
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
-pub enum Network {
-    /// Mainnet network
-    Mainnet,
-    /// Sepolia network
-    #[default]
-    Sepolia,
-}
-
 /// Errors that the client can return
 #[derive(Debug, Error)]
 enum ClientError {
@@ -60,47 +61,11 @@ enum ClientError {
     Provider(#[from] ProviderError),
 }
 
-/// Utility function to resolve the ERC1155 class hash
-fn class_hash(network: &Network) -> FieldElement {
-    match network {
-        Network::Mainnet => panic!("Network not supported yet"),
-        Network::Sepolia => {
-            felt!("0x0120d1f2225704b003e77077b8507907d2a84239bef5e0abb67462495edd644f")
-        }
-    }
-}
-
-/// Utility function to resolve the client
-fn client(network: &Network) -> Arc<JsonRpcClient<HttpTransport>> {
-    let client = match network {
-        Network::Mainnet => panic!("Network not supported yet"),
-        Network::Sepolia => JsonRpcClient::new(HttpTransport::new(
-            Url::parse("https://starknet-sepolia.public.blastapi.io/rpc/v0_6").unwrap(),
-        )),
-    };
-    Arc::new(client)
-}
-
-/// Utility function to resolve the chain ID
-fn chain_id(network: &Network) -> FieldElement {
-    match network {
-        Network::Mainnet => chain_id::MAINNET,
-        Network::Sepolia => short_string!("SN_SEPOLIA"),
-    }
-}
-
-/// Utility to resolve a keystore from a path
-fn resolve_keystore(path: &PathBuf) -> LocalWallet {
-    let keystore = PathBuf::from(path);
-
-    if !keystore.exists() {
-        panic!("keystore file not found");
-    }
-
-    let password = rpassword::prompt_password("Enter keystore password: ").unwrap();
-
-    let key = SigningKey::from_keystore(keystore, &password).unwrap();
-    LocalWallet::from_signing_key(key)
+/// Builds the RPC client for a resolved network configuration.
+fn client(config: &NetworkConfig) -> Arc<JsonRpcClient<HttpTransport>> {
+    Arc::new(JsonRpcClient::new(HttpTransport::new(
+        Url::parse(&config.rpc_url).unwrap(),
+    )))
 }
 
 /// Utility function to watch for a transaction
@@ -134,39 +99,68 @@ async fn watch_tx(
     }
 }
 
-fn tokens_to_felts(token_names: &Vec<String>) -> Vec<FieldElement> {
-    let mut tokens = vec![];
-    for token_name in token_names {
-        let mut bytes = [0u8; 32];
-        hex::decode_to_slice(token_name.clone(), &mut bytes as &mut [u8]).unwrap();
-        let value = U256 {
-            high: u128::from_be_bytes(bytes[16..].try_into().unwrap()),
-            low: u128::from_be_bytes(bytes[..16].try_into().unwrap()),
-        };
-        tokens.push(value);
+/// Parses a 32-byte hex-encoded token name into the `U256` token id the contract expects.
+fn token_id_from_name(token_name: &str) -> U256 {
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(token_name, &mut bytes as &mut [u8]).unwrap();
+    U256 {
+        high: u128::from_be_bytes(bytes[16..].try_into().unwrap()),
+        low: u128::from_be_bytes(bytes[..16].try_into().unwrap()),
     }
-    Vec::<U256>::cairo_serialize(&tokens)
 }
 
-/// Deploys a ERC-1155 contract to the specified network, using an account address, a keystore
-/// path, a recipient and a contract spec.
+fn token_ids_from_names(token_names: &[String]) -> Vec<U256> {
+    token_names.iter().map(|name| token_id_from_name(name)).collect()
+}
+
+/// Derives a deployment salt deterministically from a `ContractSpec`, so deploying the same
+/// spec twice predicts the same contract address instead of landing at a fresh one every time.
+fn derive_salt(spec: &ContractSpec) -> FieldElement {
+    let mut elements = ByteArray::cairo_serialize(&ByteArray::from_string(&spec.base_uri).unwrap());
+
+    let mut tokens: Vec<&ContractTokensInfo> = spec.tokens.iter().collect();
+    tokens.sort_by(|a, b| a.name.cmp(&b.name));
+    for token in tokens {
+        elements.append(&mut U256::cairo_serialize(&token_id_from_name(&token.name)));
+        elements.push(FieldElement::from(token.value));
+    }
+
+    compute_hash_on_elements(&elements)
+}
+
+/// Deploys a ERC-1155 contract to the specified network, using an account address, a signer, a
+/// recipient and a contract spec.
+///
+/// The deployment salt is derived deterministically from `spec` (see [`derive_salt`]) unless
+/// `salt_override` is given, so re-running with the same spec predicts the same address. If a
+/// contract with the expected class hash is already deployed there, the deployment is skipped.
+/// The transaction pays its fee in `fee_token` (legacy v1/ETH or v3/STRK), capped at the
+/// estimated fee times `fee_multiplier`; the account's balance in that token is checked first so
+/// an insufficient balance fails fast instead of via an opaque node error. When `dry_run` is set,
+/// the predicted address and estimated fee are printed and nothing is broadcast.
+#[allow(clippy::too_many_arguments)]
 pub async fn deploy_contract(
-    network: &Network,
+    network: &NetworkConfig,
     address: &str,
-    keystore_path: &PathBuf,
+    signer: Signer,
+    account_type: AccountType,
     recipient: &str,
     spec: &ContractSpec,
+    salt_override: Option<FieldElement>,
+    fee_token: FeeToken,
+    fee_multiplier: f64,
+    dry_run: bool,
 ) {
     let client = client(network);
-    let class_hash = class_hash(network);
-    let signer = resolve_keystore(keystore_path);
+    let class_hash = network.class_hash;
+    let account_address = FieldElement::from_str(address).unwrap();
 
     let mut account = SingleOwnerAccount::new(
         client.clone(),
         signer,
-        FieldElement::from_str(address).unwrap(),
-        chain_id(network),
-        ExecutionEncoding::New,
+        account_address,
+        network.chain_id,
+        account_type.execution_encoding(),
     );
 
     // `SingleOwnerAccount` defaults to checking nonce and estimating fees against the latest
@@ -178,7 +172,7 @@ pub async fn deploy_contract(
     let account = Arc::new(account);
 
     let contract_factory = ContractFactory::new(class_hash, account);
-    let salt = SigningKey::from_random().secret_scalar();
+    let salt = salt_override.unwrap_or_else(|| derive_salt(spec));
     let mut ctor_args = vec![];
 
     // Create the constructor arguments
@@ -186,13 +180,14 @@ pub async fn deploy_contract(
     ctor_args.append(&mut ByteArray::cairo_serialize(&byte_array));
     ctor_args.push(FieldElement::from_hex_be(recipient).unwrap());
 
-    ctor_args.append(&mut tokens_to_felts(
-        &spec
-            .tokens
-            .iter()
-            .map(|token_info| token_info.name.clone())
-            .collect(),
-    ));
+    let token_names: Vec<String> = spec
+        .tokens
+        .iter()
+        .map(|token_info| token_info.name.clone())
+        .collect();
+    ctor_args.append(&mut Vec::<U256>::cairo_serialize(&token_ids_from_names(
+        &token_names,
+    )));
 
     let mut values = vec![];
     for token in &spec.tokens {
@@ -204,23 +199,99 @@ pub async fn deploy_contract(
     }
     ctor_args.append(&mut Vec::<U256>::cairo_serialize(&values));
 
-    let contract_deployment = contract_factory
-        .deploy(ctor_args, salt, true)
-        .max_fee(FieldElement::from(400000000000000_u128)); // Fixme, what value is suitable?
-    let deployed_address = contract_deployment.deployed_address();
-    let estimated_fee = contract_deployment.estimate_fee().await.unwrap();
-    eprintln!(
-        "Deploying class {} with salt {}, estimated fee {}...",
-        format!("{:#064x}", class_hash),
-        format!("{:#064x}", salt),
-        format!("{:#064x}", estimated_fee.overall_fee)
-    );
-    eprintln!(
-        "The contract will be deployed at address {}",
-        format!("{:#064x}", deployed_address)
-    );
+    // `unique = false`: the UDC is asked to derive the address purely from class hash, salt and
+    // constructor calldata, so the same spec always predicts the same address regardless of
+    // which account sends the deployment transaction.
+    let deployed_address =
+        get_udc_deployed_address(salt, class_hash, &UdcUniqueness::NotUnique, &ctor_args);
+
+    match client.get_class_hash_at(BlockId::Tag(BlockTag::Pending), deployed_address).await {
+        Ok(existing_class_hash) if existing_class_hash == class_hash => {
+            eprintln!(
+                "Contract already deployed at {} with the expected class hash, skipping",
+                format!("{:#064x}", deployed_address)
+            );
+            return;
+        }
+        Ok(_) | Err(ProviderError::StarknetError(StarknetError::ContractNotFound)) => {}
+        Err(err) => panic!("failed to check for an existing deployment: {err}"),
+    }
+
+    let balance = fee_token_balance(&client, fee_token, account_address).await;
+
+    let deployment_tx = match fee_token {
+        FeeToken::Eth => {
+            let contract_deployment = contract_factory
+                .deploy_v1(ctor_args, salt, false)
+                .fee_estimate_multiplier(fee_multiplier);
+
+            let estimated_fee = contract_deployment.estimate_fee().await.unwrap();
+            eprintln!(
+                "Deploying class {} with salt {}, estimated fee {} wei (ETH)...",
+                format!("{:#064x}", class_hash),
+                format!("{:#064x}", salt),
+                estimated_fee.overall_fee
+            );
+            eprintln!(
+                "The contract will be deployed at address {}",
+                format!("{:#064x}", deployed_address)
+            );
+
+            if dry_run {
+                eprintln!("Dry run requested, not broadcasting the deployment transaction");
+                return;
+            }
+
+            // The transaction is capped at `estimated_fee * fee_multiplier`, not the bare
+            // estimate, so that's what the balance needs to cover.
+            let capped_fee = (estimated_fee.overall_fee as f64 * fee_multiplier) as u128;
+            if balance < FieldElement::from(capped_fee) {
+                panic!(
+                    "account {:#064x} has insufficient ETH balance to pay for deployment: \
+                     have {:#x}, need at least {:#x}",
+                    account_address, balance, capped_fee
+                );
+            }
+
+            contract_deployment.send().await.unwrap().transaction_hash
+        }
+        FeeToken::Strk => {
+            let contract_deployment = contract_factory
+                .deploy_v3(ctor_args, salt, false)
+                .gas_estimate_multiplier(fee_multiplier);
+
+            let estimated_fee = contract_deployment.estimate_fee().await.unwrap();
+            eprintln!(
+                "Deploying class {} with salt {}, estimated fee {} fri (STRK)...",
+                format!("{:#064x}", class_hash),
+                format!("{:#064x}", salt),
+                estimated_fee.overall_fee
+            );
+            eprintln!(
+                "The contract will be deployed at address {}",
+                format!("{:#064x}", deployed_address)
+            );
+
+            if dry_run {
+                eprintln!("Dry run requested, not broadcasting the deployment transaction");
+                return;
+            }
+
+            // The transaction is capped at `estimated_fee * fee_multiplier`, not the bare
+            // estimate, so that's what the balance needs to cover.
+            let capped_fee = (estimated_fee.overall_fee as f64 * fee_multiplier) as u128;
+            if balance < FieldElement::from(capped_fee) {
+                panic!(
+                    "account {:#064x} has insufficient STRK balance to pay for deployment: \
+                     have {:#x}, need at least {:#x}",
+                    account_address, balance, capped_fee
+                );
+            }
+
+            contract_deployment.send().await.unwrap().transaction_hash
+        }
+    };
 
-    let deployment_tx = contract_deployment.send().await.unwrap().transaction_hash;
     eprintln!(
         "Contract deployment transaction: {}",
         format!("{:#064x}", deployment_tx)
@@ -234,12 +305,26 @@ pub async fn deploy_contract(
         .unwrap();
 }
 
-/// Shows the account balance for a set of tokens
+/// Parses a `--block` argument into a `BlockId`. Accepts the tags `latest`/`pending`, a decimal
+/// block number, or a `0x`-prefixed block hash.
+pub fn parse_block_id(input: &str) -> BlockId {
+    match input {
+        "latest" => BlockId::Tag(BlockTag::Latest),
+        "pending" => BlockId::Tag(BlockTag::Pending),
+        _ => match input.parse::<u64>() {
+            Ok(number) => BlockId::Number(number),
+            Err(_) => BlockId::Hash(FieldElement::from_hex_be(input).unwrap()),
+        },
+    }
+}
+
+/// Shows the account balance for a set of tokens at the given block
 pub async fn show_contract(
-    network: &Network,
+    network: &NetworkConfig,
     contract_address: &String,
     accounts: &Vec<String>,
     tokens: Vec<String>,
+    block_id: BlockId,
 ) {
     let client = client(network);
     let contract_address = FieldElement::from_hex_be(contract_address).unwrap();
@@ -247,20 +332,13 @@ pub async fn show_contract(
         .iter()
         .map(|account| FieldElement::from_hex_be(account).unwrap())
         .collect();
-    let selector = get_selector_from_name("balance_of_batch").unwrap();
-
-    let mut calldata = Vec::<FieldElement>::cairo_serialize(&account_felts);
-    calldata.append(&mut tokens_to_felts(&tokens));
+    let token_ids = token_ids_from_names(&tokens);
 
-    let result = client
-        .call(
-            FunctionCall {
-                contract_address,
-                entry_point_selector: selector,
-                calldata,
-            },
-            BlockId::Tag(BlockTag::Pending),
-        )
+    let reader = Erc1155Reader::new(contract_address, client);
+    let result = reader
+        .balance_of_batch(&account_felts, &token_ids)
+        .block_id(block_id)
+        .call()
         .await
         .unwrap();
 
@@ -271,8 +349,9 @@ pub async fn show_contract(
 
         for (ind_element, element) in result.iter().enumerate() {
             println!(
-                "    \"{:#064x}\"{}",
-                element,
+                "    \"0x{:032x}{:032x}\"{}",
+                element.high,
+                element.low,
                 if ind_element == result.len() - 1 {
                     ""
                 } else {
@@ -1,7 +1,13 @@
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
-use cofiblocks_contract::{deploy_contract, show_contract, ContractSpec, Network};
+use starknet::core::types::FieldElement;
+
+use cofiblocks_contract::{
+    deploy_contract, events::ScanFilter, index_transfers, parse_block_id, print_transfers_report,
+    resolve_network_config, resolve_signer, show_contract, AccountType, ContractSpec, FeeToken,
+    Network, NetworkOverrides, SignerBackend,
+};
 
 /// Command line arguments for the binary
 #[derive(Parser, Debug)]
@@ -10,6 +16,15 @@ struct Args {
     #[command(subcommand)]
     command: Commands,
     network: Option<Network>,
+    /// Overrides the network's RPC endpoint, required for `--network custom`
+    #[arg(long)]
+    rpc_url: Option<String>,
+    /// Overrides the network's chain id (as a Cairo short string), required for `--network custom`
+    #[arg(long)]
+    chain_id: Option<String>,
+    /// Overrides the network's ERC-1155 class hash, required for `--network custom`
+    #[arg(long)]
+    class_hash: Option<String>,
 }
 
 #[derive(Debug, Subcommand)]
@@ -22,6 +37,9 @@ enum Commands {
         account: String,
         /// Shows all tokens in the contract specification
         spec: PathBuf,
+        /// Block to query: a number, a block hash, or `latest`/`pending`
+        #[arg(long, default_value = "pending")]
+        block: String,
     },
     #[command(arg_required_else_help = true)]
     Show {
@@ -31,6 +49,9 @@ enum Commands {
         account: String,
         /// Token ID to show
         token: String,
+        /// Block to query: a number, a block hash, or `latest`/`pending`
+        #[arg(long, default_value = "pending")]
+        block: String,
     },
     #[command(arg_required_else_help = true)]
     Deploy {
@@ -38,10 +59,49 @@ enum Commands {
         spec: PathBuf,
         /// Signing account address
         address: String,
-        /// Keystore path
-        keystore: PathBuf,
         /// Recipient of minted tokens
         recipient: String,
+        /// Which signer backend to use (Ledger hardware-wallet support is deferred, see signer.rs)
+        #[arg(long, value_enum, default_value = "keystore")]
+        signer: SignerBackend,
+        /// Which account contract variant `address` is
+        #[arg(long, value_enum, default_value = "oz")]
+        account_type: AccountType,
+        /// Keystore path, required when `--signer keystore`
+        #[arg(long)]
+        keystore: Option<PathBuf>,
+        /// Overrides the derived deployment salt with an explicit felt
+        #[arg(long)]
+        salt: Option<String>,
+        /// Which token the deployment transaction pays its fee in
+        #[arg(long, value_enum, default_value = "eth")]
+        fee_token: FeeToken,
+        /// Safety multiplier applied to the estimated fee before capping the transaction
+        #[arg(long, default_value_t = 1.5)]
+        fee_multiplier: f64,
+        /// Prints the predicted address and estimated fee without broadcasting
+        #[arg(long)]
+        dry_run: bool,
+    },
+    #[command(arg_required_else_help = true)]
+    Scan {
+        /// Contract address to scan for transfer events
+        contract_address: String,
+        /// First block to scan (a number, a block hash, or `latest`/`pending`)
+        #[arg(long, default_value = "0")]
+        from_block: String,
+        /// Last block to scan (a number, a block hash, or `latest`/`pending`)
+        #[arg(long, default_value = "latest")]
+        to_block: String,
+        /// Only report transfers touching this account
+        #[arg(long)]
+        account: Option<String>,
+        /// Only report transfers of this token id
+        #[arg(long)]
+        token: Option<String>,
+        /// Prints the report as JSON instead of a human-readable table
+        #[arg(long)]
+        json: bool,
     },
 }
 
@@ -49,13 +109,24 @@ enum Commands {
 async fn main() {
     let args = Args::parse();
 
-    let network = args.network.unwrap_or_default();
+    let class_hash = args
+        .class_hash
+        .map(|class_hash| FieldElement::from_hex_be(&class_hash).unwrap());
+    let network = resolve_network_config(
+        args.network.unwrap_or_default(),
+        NetworkOverrides {
+            rpc_url: args.rpc_url,
+            chain_id: args.chain_id,
+            class_hash,
+        },
+    );
 
     match args.command {
         Commands::ShowAll {
             contract_address,
             account,
             spec,
+            block,
         } => {
             if !spec.exists() {
                 panic!("Spec file not found");
@@ -67,18 +138,41 @@ async fn main() {
                 .iter()
                 .map(|token_info| token_info.name.clone())
                 .collect();
-            show_contract(&network, &contract_address, &[account], &tokens).await
+            show_contract(
+                &network,
+                &contract_address,
+                &[account],
+                tokens,
+                parse_block_id(&block),
+            )
+            .await
         }
         Commands::Show {
             contract_address,
             account,
             token,
-        } => show_contract(&network, &contract_address, &[account], &[token]).await,
+            block,
+        } => {
+            show_contract(
+                &network,
+                &contract_address,
+                &[account],
+                vec![token],
+                parse_block_id(&block),
+            )
+            .await
+        }
         Commands::Deploy {
             spec,
             address,
-            keystore,
             recipient,
+            signer,
+            account_type,
+            keystore,
+            salt,
+            fee_token,
+            fee_multiplier,
+            dry_run,
         } => {
             if !spec.exists() {
                 panic!("Spec file not found");
@@ -88,7 +182,45 @@ async fn main() {
             println!("{:?}", result);
             let spec = result.unwrap();
             //let spec = toml::from_str(&file_content).unwrap();
-            deploy_contract(&network, &address, &keystore, &recipient, &spec).await
+            let salt = salt.map(|salt| FieldElement::from_hex_be(&salt).unwrap());
+            let signer =
+                resolve_signer(signer, keystore.as_ref()).expect("failed to resolve signer");
+            deploy_contract(
+                &network,
+                &address,
+                signer,
+                account_type,
+                &recipient,
+                &spec,
+                salt,
+                fee_token,
+                fee_multiplier,
+                dry_run,
+            )
+            .await
+        }
+        Commands::Scan {
+            contract_address,
+            from_block,
+            to_block,
+            account,
+            token,
+            json,
+        } => {
+            let contract_address = FieldElement::from_hex_be(&contract_address).unwrap();
+            let filter = ScanFilter {
+                account: account.map(|account| FieldElement::from_hex_be(&account).unwrap()),
+                token,
+            };
+            let transfers = index_transfers(
+                &network,
+                contract_address,
+                parse_block_id(&from_block),
+                parse_block_id(&to_block),
+                &filter,
+            )
+            .await;
+            print_transfers_report(&transfers, json);
         }
     }
 }
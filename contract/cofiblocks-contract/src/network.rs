@@ -0,0 +1,104 @@
+//! Network registry.
+//!
+//! Maps each supported network to its RPC URL, chain id, and ERC-1155 class hash, loaded from
+//! `networks.toml` (embedded into the binary at compile time) instead of hard-coded matches.
+//! `--rpc-url`/`--chain-id`/`--class-hash` override the registry per invocation, and are the
+//! only way to configure `Network::Custom`, which has no built-in entry.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use starknet::core::{types::FieldElement, utils::cairo_short_string_to_felt};
+
+/// Supported Starknet networks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Network {
+    /// Mainnet network
+    Mainnet,
+    /// Sepolia network
+    #[default]
+    Sepolia,
+    /// A network with no built-in entry; configure it with `--rpc-url`, `--chain-id` and
+    /// `--class-hash`.
+    Custom,
+}
+
+impl Network {
+    fn registry_key(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Sepolia => "sepolia",
+            Network::Custom => "custom",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct NetworkEntry {
+    rpc_url: String,
+    chain_id: String,
+    /// Absent for networks (e.g. Mainnet) where no class has been declared yet; `--class-hash`
+    /// is then required.
+    class_hash: Option<String>,
+}
+
+/// Built-in network registry, embedded into the binary at compile time.
+const NETWORKS_TOML: &str = include_str!("../networks.toml");
+
+fn builtin_registry() -> HashMap<String, NetworkEntry> {
+    toml::from_str(NETWORKS_TOML).expect("networks.toml is malformed")
+}
+
+/// Resolved settings for whichever network the CLI was pointed at, after applying any
+/// `--rpc-url`/`--chain-id`/`--class-hash` overrides.
+#[derive(Debug, Clone)]
+pub struct NetworkConfig {
+    pub rpc_url: String,
+    pub chain_id: FieldElement,
+    pub class_hash: FieldElement,
+}
+
+/// Overrides for a single network's registry entry, straight from the matching CLI flags.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkOverrides {
+    pub rpc_url: Option<String>,
+    pub chain_id: Option<String>,
+    pub class_hash: Option<FieldElement>,
+}
+
+/// Resolves a [`NetworkConfig`] for `network`, preferring `overrides` over the built-in
+/// registry entry, and requiring the relevant override for any field the registry doesn't have
+/// (always the case for `Network::Custom`).
+pub fn resolve_network_config(network: Network, overrides: NetworkOverrides) -> NetworkConfig {
+    let registry = builtin_registry();
+    let entry = registry.get(network.registry_key());
+
+    let rpc_url = overrides
+        .rpc_url
+        .or_else(|| entry.map(|entry| entry.rpc_url.clone()))
+        .unwrap_or_else(|| panic!("--rpc-url is required for the {network:?} network"));
+
+    let chain_id = overrides
+        .chain_id
+        .as_deref()
+        .map(|chain_id| cairo_short_string_to_felt(chain_id).unwrap())
+        .or_else(|| {
+            entry.map(|entry| cairo_short_string_to_felt(&entry.chain_id).unwrap())
+        })
+        .unwrap_or_else(|| panic!("--chain-id is required for the {network:?} network"));
+
+    let class_hash = overrides
+        .class_hash
+        .or_else(|| {
+            entry
+                .and_then(|entry| entry.class_hash.as_deref())
+                .map(|class_hash| FieldElement::from_hex_be(class_hash).unwrap())
+        })
+        .unwrap_or_else(|| panic!("--class-hash is required for the {network:?} network"));
+
+    NetworkConfig {
+        rpc_url,
+        chain_id,
+        class_hash,
+    }
+}
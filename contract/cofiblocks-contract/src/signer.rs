@@ -0,0 +1,137 @@
+//! Pluggable signer backends and account contract variants.
+//!
+//! `resolve_keystore` used to be the only way to sign: an encrypted JSON keystore driving a
+//! `SingleOwnerAccount`. [`SignerBackend`] generalizes that into keystore / raw private key,
+//! selectable on the CLI, while [`AccountType`] captures the account-contract-specific bits
+//! (execution encoding today; constructor/validation quirks as they come up) so deployment logic
+//! stays decoupled from key management.
+//!
+//! A Ledger hardware wallet backend is intentionally not one of them yet. A first attempt spoke a
+//! hand-rolled APDU protocol (made-up CLA/INS bytes, a single-exchange sign flow) with no
+//! reference to verify it against the real Starknet Ledger app, so it would not have
+//! interoperated with actual hardware, and was removed rather than shipped behind `--signer
+//! ledger`. Ledger support is deferred — `--signer` only offers `keystore`/`env` for now — until
+//! it can be built on a maintained Starknet Ledger transport instead of a guessed wire format.
+
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use starknet::{
+    accounts::ExecutionEncoding,
+    core::types::FieldElement,
+    signers::{LocalWallet, Signer as StarknetSigner, SigningKey, VerifyingKey},
+};
+
+/// Which signing backend to use for a transaction.
+///
+/// A Ledger hardware-wallet backend is deferred (see the module docs) and deliberately not a
+/// variant here yet, rather than shipping an unverifiable implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum SignerBackend {
+    /// Encrypted JSON keystore on disk (the original, and still default, behavior).
+    #[default]
+    Keystore,
+    /// Raw private key read from the `STARKNET_PRIVATE_KEY` environment variable.
+    Env,
+}
+
+/// Which account contract variant is signing, so `ExecutionEncoding` and any account-specific
+/// constructor/validation differences are handled correctly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum AccountType {
+    /// OpenZeppelin's reference account contract.
+    #[default]
+    Oz,
+    /// Argent account contract.
+    Argent,
+    /// Braavos account contract.
+    Braavos,
+}
+
+impl AccountType {
+    /// The `ExecutionEncoding` this account variant expects its calldata in.
+    pub fn execution_encoding(&self) -> ExecutionEncoding {
+        match self {
+            // All three are Cairo 1 / SNIP-6 accounts whose `__execute__` takes `Array<Call>`.
+            // `ExecutionEncoding::Legacy` is the Cairo 0 `(call_array, calldata, nonce)` format,
+            // which none of these variants model.
+            AccountType::Oz | AccountType::Argent | AccountType::Braavos => {
+                ExecutionEncoding::New
+            }
+        }
+    }
+}
+
+/// A signer resolved from a [`SignerBackend`], usable anywhere `starknet::signers::Signer` is
+/// expected (e.g. in a `SingleOwnerAccount`).
+pub enum Signer {
+    Local(LocalWallet),
+}
+
+/// Errors produced while signing or resolving a signer.
+#[derive(Debug, thiserror::Error)]
+pub enum SignerError {
+    #[error("keystore file not found")]
+    KeystoreNotFound,
+    #[error("STARKNET_PRIVATE_KEY is not set")]
+    EnvKeyNotSet,
+    #[error("invalid private key: {0}")]
+    InvalidPrivateKey(String),
+}
+
+/// Resolves a [`SignerBackend`] into a usable [`Signer`].
+///
+/// `keystore_path` is only consulted for `SignerBackend::Keystore`.
+pub fn resolve_signer(
+    backend: SignerBackend,
+    keystore_path: Option<&PathBuf>,
+) -> Result<Signer, SignerError> {
+    match backend {
+        SignerBackend::Keystore => {
+            let path = keystore_path.expect("--keystore is required for the keystore signer");
+            if !path.exists() {
+                return Err(SignerError::KeystoreNotFound);
+            }
+
+            let password = rpassword::prompt_password("Enter keystore password: ").unwrap();
+            let key = SigningKey::from_keystore(path, &password).unwrap();
+            Ok(Signer::Local(LocalWallet::from_signing_key(key)))
+        }
+        SignerBackend::Env => {
+            let raw_key =
+                std::env::var("STARKNET_PRIVATE_KEY").map_err(|_| SignerError::EnvKeyNotSet)?;
+            let key = SigningKey::from_secret_scalar(
+                FieldElement::from_hex_be(&raw_key)
+                    .map_err(|err| SignerError::InvalidPrivateKey(err.to_string()))?,
+            );
+            Ok(Signer::Local(LocalWallet::from_signing_key(key)))
+        }
+    }
+}
+
+#[async_trait]
+impl StarknetSigner for Signer {
+    type GetPublicKeyError = SignerError;
+    type SignError = SignerError;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        match self {
+            Signer::Local(signer) => Ok(signer.get_public_key().await.unwrap()),
+        }
+    }
+
+    async fn sign_hash(
+        &self,
+        hash: &FieldElement,
+    ) -> Result<starknet::core::crypto::Signature, Self::SignError> {
+        match self {
+            Signer::Local(signer) => Ok(signer.sign_hash(hash).await.unwrap()),
+        }
+    }
+
+    fn is_interactive(&self) -> bool {
+        match self {
+            Signer::Local(signer) => signer.is_interactive(),
+        }
+    }
+}
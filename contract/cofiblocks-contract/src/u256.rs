@@ -1,13 +1,15 @@
 use cainome_cairo_serde::{CairoSerde, Error};
 use starknet::core::types::FieldElement;
 
-#[derive(Debug, Clone)]
-/// Represents an unsigned integer of 256 bits
-pub(crate) struct U256 {
+/// Represents an unsigned integer of 256 bits, shared by the constructor-argument encoder and
+/// the raw event decoder (the generated `erc1155` bindings carry their own `U256` for typed
+/// contract calls). Public because it appears in the `events` module's public `Transfer` type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct U256 {
     /// Lower 128 bits
-    pub(crate) low: u128,
+    pub low: u128,
     /// Upper 128 bits
-    pub(crate) high: u128,
+    pub high: u128,
 }
 
 impl CairoSerde for U256 {